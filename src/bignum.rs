@@ -1,19 +1,35 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
 use std::str::FromStr;
 
+use crate::Num;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BigNum {
     left: Vec<u8>,
     right: Vec<u8>,
+    negative: bool,
 }
 
 static U4_MASK: u8 = 0b1111;
 
+/// Number of fractional digits `Div` keeps when a division does not
+/// terminate exactly (e.g. repeating decimals like `1/3`).
+pub const DEFAULT_PRECISION: usize = 20;
+
+impl Default for BigNum {
+    fn default() -> Self {
+        BigNum::new()
+    }
+}
+
 impl BigNum {
     pub fn new() -> BigNum {
         BigNum {
             left: Vec::new(),
             right: Vec::new(),
+            negative: false,
         }
     }
 
@@ -24,9 +40,16 @@ impl BigNum {
         BigNum {
             left,
             right,
+            negative: false,
         }
     }
 
+    pub fn from_raw_signed(left: Vec<u8>, right: Vec<u8>, negative: bool) -> BigNum {
+        let mut num = BigNum::from_raw(left, right);
+        num.negative = negative && !num.is_zero();
+        num
+    }
+
     fn trim_data(data: &mut Vec<u8>) {
         let mut to_trim = 0usize;
         for v in data.iter().rev() {
@@ -40,58 +63,396 @@ impl BigNum {
         data.truncate(data.len() - to_trim);
     }
 
-    pub fn add_left(&mut self, other: &[u8]) {
-        let mut carry = 0;
-        for i in 0..other.len() {
-            if self.left.len() > i {
-                let tmp = other[i] + self.left[i] + carry;
-                carry = if tmp > 9 { 1 } else { 0 };
-                self.left[i] = tmp & U4_MASK;
+    pub fn is_zero(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigNum {
+        BigNum {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            negative: false,
+        }
+    }
+
+    /// Splits the magnitude into a single little-endian digit run (least
+    /// significant digit first) along with how many of its least-significant digits are
+    /// fractional, so multiplication/division can treat the value as a plain
+    /// scaled integer.
+    fn to_little_endian(&self) -> (Vec<u8>, usize) {
+        let mut digits: Vec<u8> = self.right.iter().rev().cloned().collect();
+        digits.extend(self.left.iter().cloned());
+        (digits, self.right.len())
+    }
+
+    /// Inverse of `to_little_endian`: reassembles `left`/`right` from a
+    /// little-endian digit run given how many of its low digits are
+    /// fractional.
+    fn from_little_endian(mut digits: Vec<u8>, decimal_places: usize, negative: bool) -> BigNum {
+        while digits.len() < decimal_places {
+            digits.push(0);
+        }
+
+        let right: Vec<u8> = digits[0..decimal_places].iter().rev().cloned().collect();
+        let left: Vec<u8> = digits[decimal_places..].to_vec();
+
+        BigNum::from_raw_signed(left, right, negative)
+    }
+
+    /// Compares two magnitudes (ignoring sign).
+    fn compare_magnitude(a: &BigNum, b: &BigNum) -> Ordering {
+        match a.left.len().cmp(&b.left.len()) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        for i in (0..a.left.len()).rev() {
+            match a.left[i].cmp(&b.left[i]) {
+                Ordering::Equal => continue,
+                ord => return ord,
+            }
+        }
+
+        let max_right = a.right.len().max(b.right.len());
+        for i in 0..max_right {
+            let da = a.right.get(i).copied().unwrap_or(0);
+            let db = b.right.get(i).copied().unwrap_or(0);
+            match da.cmp(&db) {
+                Ordering::Equal => continue,
+                ord => return ord,
+            }
+        }
+
+        Ordering::Equal
+    }
+
+    /// Adds two same-signed magnitudes, little-endian digit by digit.
+    fn magnitude_add(a: &BigNum, b: &BigNum) -> BigNum {
+        let (a_digits, a_places) = a.to_little_endian();
+        let (b_digits, b_places) = b.to_little_endian();
+        let places = a_places.max(b_places);
+
+        let mut a_digits = a_digits;
+        let mut b_digits = b_digits;
+        a_digits.splice(0..0, std::iter::repeat_n(0, places - a_places));
+        b_digits.splice(0..0, std::iter::repeat_n(0, places - b_places));
+
+        let len = a_digits.len().max(b_digits.len());
+        let mut result = Vec::with_capacity(len + 1);
+        let mut carry = 0u8;
+        for i in 0..len {
+            let da = a_digits.get(i).copied().unwrap_or(0);
+            let db = b_digits.get(i).copied().unwrap_or(0);
+            let sum = da + db + carry;
+            result.push(sum % 10);
+            carry = sum / 10;
+        }
+        if carry > 0 {
+            result.push(carry);
+        }
+
+        BigNum::from_little_endian(result, places, false)
+    }
+
+    /// Subtracts the smaller magnitude `b` from the larger magnitude `a`,
+    /// with a borrow propagated across the combined digit run.
+    fn magnitude_sub(a: &BigNum, b: &BigNum) -> BigNum {
+        let (a_digits, a_places) = a.to_little_endian();
+        let (b_digits, b_places) = b.to_little_endian();
+        let places = a_places.max(b_places);
+
+        let mut a_digits = a_digits;
+        let mut b_digits = b_digits;
+        a_digits.splice(0..0, std::iter::repeat_n(0, places - a_places));
+        b_digits.splice(0..0, std::iter::repeat_n(0, places - b_places));
+
+        let mut result = Vec::with_capacity(a_digits.len());
+        let mut borrow = 0i8;
+        for (i, &ad) in a_digits.iter().enumerate() {
+            let da = ad as i8;
+            let db = b_digits.get(i).copied().unwrap_or(0) as i8;
+            let mut diff = da - db - borrow;
+            if diff < 0 {
+                diff += 10;
+                borrow = 1;
             } else {
-                let tmp = other[i] + carry;
-                carry = if tmp > 9 { 1 } else { 0 };
-                self.left.push(tmp & U4_MASK);
+                borrow = 0;
             }
+            result.push(diff as u8);
         }
+
+        BigNum::from_little_endian(result, places, false)
     }
 
-    pub fn add_right(&mut self, other: &[u8]) {
-        let offset;
-        if other.len() > self.right.len() {
-            let dif = other.len() - self.right.len();
-            for i in /*other.len()-1-*/dif..other.len() {
-                self.right.push(other[i]); // TODO: optimization?
+    fn multiply_magnitudes(a: &[u8], b: &[u8]) -> Vec<u8> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+
+        let mut result = vec![0u16; a.len() + b.len()];
+        for (i, &da) in a.iter().enumerate() {
+            let mut carry = 0u16;
+            for (j, &db) in b.iter().enumerate() {
+                let idx = i + j;
+                let prod = result[idx] + da as u16 * db as u16 + carry;
+                result[idx] = prod % 10;
+                carry = prod / 10;
             }
-            offset = dif; // self.right.len() - offset
-        } else {
-            offset = 0;
+            let mut k = i + b.len();
+            while carry > 0 {
+                let sum = result[k] + carry;
+                result[k] = sum % 10;
+                carry = sum / 10;
+                k += 1;
+            }
+        }
+
+        let mut out: Vec<u8> = result.into_iter().map(|d| d as u8).collect();
+        BigNum::trim_data(&mut out);
+        out
+    }
+
+    /// Long division to `precision` fractional digits. Returns `None` when
+    /// dividing by zero. Never loops forever on a repeating decimal: it
+    /// always stops after `precision` fractional digits, truncating toward
+    /// zero.
+    pub fn div_with_precision(&self, other: &BigNum, precision: usize) -> Option<BigNum> {
+        if other.is_zero() {
+            return None;
+        }
+        if self.is_zero() {
+            return Some(BigNum::new());
         }
 
-        let mut carry = 0;
-        for i in (0..other.len()-offset).rev() {
-            if i > 0 {
-                let tmp = other[i] + self.right[i] + carry;
-                carry = if tmp > 9 { 1 } else { 0 };
-                self.right[i] = tmp & U4_MASK;
+        let (self_digits, self_places) = self.to_little_endian();
+        let (other_digits, other_places) = other.to_little_endian();
+        let places = self_places.max(other_places);
+
+        let mut dividend_be: Vec<u8> = self_digits.iter().rev().cloned().collect();
+        let mut divisor_be: Vec<u8> = other_digits.iter().rev().cloned().collect();
+        dividend_be.extend(std::iter::repeat_n(0, places - self_places));
+        divisor_be.extend(std::iter::repeat_n(0, places - other_places));
+        if dividend_be.is_empty() {
+            dividend_be.push(0);
+        }
+        if divisor_be.is_empty() {
+            divisor_be.push(0);
+        }
+
+        let (quotient_be, _remainder) = BigNum::long_divide(&dividend_be, &divisor_be, precision);
+        let negative = self.negative != other.negative;
+
+        Some(BigNum::from_little_endian(
+            quotient_be.into_iter().rev().collect(),
+            precision,
+            negative,
+        ))
+    }
+
+    /// Divides big-endian integer digit runs `dividend` by `divisor`,
+    /// producing `dividend.len() + extra_digits` quotient digits (the first
+    /// `dividend.len()` are the integer part, the rest are fractional digits
+    /// obtained by bringing down zeros), with leading zeros stripped, plus
+    /// the final remainder.
+    fn long_divide(dividend: &[u8], divisor: &[u8], extra_digits: usize) -> (Vec<u8>, Vec<u8>) {
+        let mut remainder: Vec<u8> = vec![0];
+        let mut quotient = Vec::with_capacity(dividend.len() + extra_digits);
+
+        for idx in 0..(dividend.len() + extra_digits) {
+            let next_digit = dividend.get(idx).copied().unwrap_or(0);
+
+            if remainder == [0] {
+                remainder = vec![next_digit];
             } else {
-                let tmp = other[i] + carry;
-                carry = if tmp > 9 { 1 } else { 0 };
-                self.left.push(tmp & U4_MASK);
-                break;
+                remainder.push(next_digit);
+            }
+            while remainder.len() > 1 && remainder[0] == 0 {
+                remainder.remove(0);
+            }
+
+            let mut trial = 0u8;
+            while trial < 9 {
+                let candidate = BigNum::mul_digit_be(divisor, trial + 1);
+                if BigNum::compare_digits_be(&candidate, &remainder) != Ordering::Greater {
+                    trial += 1;
+                } else {
+                    break;
+                }
+            }
+            if trial > 0 {
+                let to_subtract = BigNum::mul_digit_be(divisor, trial);
+                remainder = BigNum::sub_digits_be(&remainder, &to_subtract);
+            }
+            quotient.push(trial);
+        }
+
+        while quotient.len() > 1 && quotient[0] == 0 {
+            quotient.remove(0);
+        }
+
+        (quotient, remainder)
+    }
+
+    fn compare_digits_be(a: &[u8], b: &[u8]) -> Ordering {
+        match a.len().cmp(&b.len()) {
+            Ordering::Equal => a.cmp(b),
+            ord => ord,
+        }
+    }
+
+    fn sub_digits_be(a: &[u8], b: &[u8]) -> Vec<u8> {
+        let mut result = vec![0u8; a.len()];
+        let mut borrow = 0i16;
+        for i in 0..a.len() {
+            let ai = a[a.len() - 1 - i] as i16;
+            let bi = b
+                .len()
+                .checked_sub(1 + i)
+                .map(|idx| b[idx] as i16)
+                .unwrap_or(0);
+            let mut diff = ai - bi - borrow;
+            if diff < 0 {
+                diff += 10;
+                borrow = 1;
+            } else {
+                borrow = 0;
             }
+            result[a.len() - 1 - i] = diff as u8;
+        }
+        while result.len() > 1 && result[0] == 0 {
+            result.remove(0);
+        }
+        result
+    }
+
+    fn mul_digit_be(a: &[u8], d: u8) -> Vec<u8> {
+        if d == 0 {
+            return vec![0];
+        }
+        let mut result = Vec::with_capacity(a.len() + 1);
+        let mut carry = 0u16;
+        for &digit in a.iter().rev() {
+            let prod = digit as u16 * d as u16 + carry;
+            result.push((prod % 10) as u8);
+            carry = prod / 10;
+        }
+        while carry > 0 {
+            result.push((carry % 10) as u8);
+            carry /= 10;
+        }
+        result.reverse();
+        while result.len() > 1 && result[0] == 0 {
+            result.remove(0);
+        }
+        result
+    }
+}
+
+impl PartialOrd for BigNum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_zero() && other.is_zero() {
+            return Some(Ordering::Equal);
+        }
+        if self.negative != other.negative {
+            return Some(if self.negative {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            });
         }
+
+        let magnitude_ord = BigNum::compare_magnitude(self, other);
+        Some(if self.negative {
+            magnitude_ord.reverse()
+        } else {
+            magnitude_ord
+        })
+    }
+}
+
+impl Add for BigNum {
+    type Output = BigNum;
+
+    fn add(self, other: Self) -> BigNum {
+        if self.negative == other.negative {
+            let mut result = BigNum::magnitude_add(&self, &other);
+            result.negative = self.negative && !result.is_zero();
+            result
+        } else if BigNum::compare_magnitude(&self, &other) == Ordering::Less {
+            let mut result = BigNum::magnitude_sub(&other, &self);
+            result.negative = other.negative && !result.is_zero();
+            result
+        } else {
+            let mut result = BigNum::magnitude_sub(&self, &other);
+            result.negative = self.negative && !result.is_zero();
+            result
+        }
+    }
+}
+
+impl Neg for BigNum {
+    type Output = BigNum;
+
+    fn neg(mut self) -> BigNum {
+        self.negative = !self.negative && !self.is_zero();
+        self
+    }
+}
+
+impl Sub for BigNum {
+    type Output = BigNum;
+
+    fn sub(self, other: Self) -> BigNum {
+        self + (-other)
+    }
+}
+
+impl Mul for BigNum {
+    type Output = BigNum;
+
+    fn mul(self, other: Self) -> BigNum {
+        let (a_digits, a_places) = self.to_little_endian();
+        let (b_digits, b_places) = other.to_little_endian();
+        let product = BigNum::multiply_magnitudes(&a_digits, &b_digits);
+        let negative = self.negative != other.negative;
+
+        BigNum::from_little_endian(product, a_places + b_places, negative)
+    }
+}
+
+impl Div for BigNum {
+    type Output = BigNum;
+
+    /// Panics on division by zero; `Interpreter`s should check for a zero
+    /// divisor themselves and surface a proper error instead of reaching
+    /// this, the same way the `f64` backend now does.
+    fn div(self, other: Self) -> BigNum {
+        self.div_with_precision(&other, DEFAULT_PRECISION)
+            .expect("division by zero")
     }
 }
 
 impl fmt::Display for BigNum {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut string = String::with_capacity(self.left.len() + 1 + self.right.len());
+        if self.negative {
+            string.push('-');
+        }
+        if self.left.is_empty() {
+            string.push('0');
+        }
         for v in self.left.iter().rev() {
             string.push((v + 48) as char);
         }
-        string.push('.');
-        for v in &self.right {
-            string.push((v + 48) as char);
+        if !self.right.is_empty() {
+            string.push('.');
+            for v in &self.right {
+                string.push((v + 48) as char);
+            }
         }
 
         write!(f, "{}", string)
@@ -102,8 +463,143 @@ impl FromStr for BigNum {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
         let divided_string: Vec<&[u8]> = s.split('.').map(|s| s.as_bytes()).collect();
+        let right = divided_string.get(1).copied().unwrap_or(&[]);
+
+        Ok(BigNum::from_raw_signed(
+            divided_string[0].iter().rev().map(|n| *n - 48).collect(),
+            right.iter().map(|n| *n - 48).collect(),
+            negative,
+        ))
+    }
+}
+
+impl BigNum {
+    /// Bridges to `f64` for the operations below that are not computed in
+    /// exact decimal (`%`, `^`, `!`, `sqrt`, `sin`, `cos`, `tan`, `log10`).
+    /// `+ - * /` stay exact.
+    fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(0.0)
+    }
+}
+
+impl Num for BigNum {
+    fn from_f64(value: f64) -> Self {
+        value
+            .to_string()
+            .parse()
+            .unwrap_or_else(|_| BigNum::new())
+    }
+
+    /// Parses `s` directly via `FromStr`, so a literal beyond `f64`'s
+    /// precision (e.g. `10000000000000001`) survives exactly instead of
+    /// rounding through `from_f64`.
+    fn from_str_exact(s: &str) -> Self {
+        s.parse().unwrap_or_else(|_| BigNum::new())
+    }
+
+    fn checked_div(self, other: Self) -> Option<Self> {
+        self.div_with_precision(&other, DEFAULT_PRECISION)
+    }
+
+    fn checked_rem(self, other: Self) -> Option<Self> {
+        if other.is_zero() {
+            None
+        } else {
+            Some(self.rem(other))
+        }
+    }
+
+    fn neg(self) -> Self {
+        -self
+    }
+
+    fn rem(self, other: Self) -> Self {
+        BigNum::from_f64(self.to_f64() % other.to_f64())
+    }
+
+    fn pow(self, exponent: Self) -> Self {
+        BigNum::from_f64(self.to_f64().powf(exponent.to_f64()))
+    }
+
+    fn checked_pow(self, exponent: Self) -> Result<Self, crate::MathError> {
+        if self.is_zero() && exponent.is_negative() {
+            Err(crate::MathError::DivideByZero)
+        } else {
+            Ok(Num::pow(self, exponent))
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        BigNum::from_f64(self.to_f64().sqrt())
+    }
+
+    fn sin(self) -> Self {
+        BigNum::from_f64(self.to_f64().sin())
+    }
+
+    fn cos(self) -> Self {
+        BigNum::from_f64(self.to_f64().cos())
+    }
+
+    fn tan(self) -> Self {
+        BigNum::from_f64(self.to_f64().tan())
+    }
+
+    fn log10(self) -> Self {
+        BigNum::from_f64(self.to_f64().log10())
+    }
+
+    fn abs(self) -> Self {
+        BigNum::abs(&self)
+    }
+
+    fn i() -> Self {
+        BigNum::new()
+    }
+
+    fn factorial(self) -> Self {
+        BigNum::from_f64(crate::computer::factorial_approx(self.to_f64()))
+    }
+
+    fn checked_factorial(self) -> Result<Self, crate::MathError> {
+        if crate::computer::is_factorial_pole(self.to_f64()) {
+            Err(crate::MathError::FactorialOfNegative)
+        } else {
+            Ok(Num::factorial(self))
+        }
+    }
+
+    fn checked_sqrt(self) -> Result<Self, crate::Function> {
+        if self.is_negative() {
+            Err(crate::Function::Sqrt)
+        } else {
+            Ok(Num::sqrt(self))
+        }
+    }
+
+    fn checked_log10(self) -> Result<Self, crate::Function> {
+        if self.is_negative() || self.is_zero() {
+            Err(crate::Function::Log)
+        } else {
+            Ok(Num::log10(self))
+        }
+    }
+
+    fn checked_tan(self) -> Result<Self, crate::Function> {
+        if crate::computer::is_tan_pole(self.to_f64()) {
+            Err(crate::Function::Tan)
+        } else {
+            Ok(Num::tan(self))
+        }
+    }
 
-        Ok(BigNum::from_raw(divided_string[0].iter().rev().map(|n| *n - 48).collect(), divided_string[1].iter().map(|n| *n - 48).collect()))
+    fn compare(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
     }
 }
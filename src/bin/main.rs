@@ -4,8 +4,8 @@ use rustyline::Editor;
 use structopt::StructOpt;
 
 use rscalc::{
-    parse, tokenize, InterpretError, Interpreter, Num, ParseError, ParseErrorCode, TokenizeError,
-    Variant,
+    parse, tokenize, BigNum, Complex, Expr, InterpretError, Interpreter, MathError, Num,
+    ParseError, ParseErrorCode, Rational, TokenizeError, Variant,
 };
 use std::fmt::Display;
 use std::ops::Range;
@@ -23,19 +23,57 @@ struct Opt {
     vars: bool,
     #[structopt(long = "no-color", help = "Prevents colored text")]
     no_color: bool,
+    #[structopt(long = "base", help = "Prints results in the given base (2-36)")]
+    base: Option<u32>,
+    #[structopt(
+        long = "exact",
+        alias = "bignum",
+        help = "Evaluates using arbitrary-precision decimal arithmetic instead of f64"
+    )]
+    exact: bool,
+    #[structopt(
+        long = "complex",
+        help = "Evaluates using complex arithmetic, so e.g. sqrt(-1) = i"
+    )]
+    complex: bool,
+    #[structopt(
+        long = "rational",
+        alias = "fraction",
+        help = "Evaluates using exact fractions, so e.g. 1/3 + 1/6 = 1/2"
+    )]
+    rational: bool,
 }
 
 fn main() {
     let opt = Opt::from_args();
 
-    let mut interpreter = Interpreter::default();
+    let base = match opt.base.map(validate_base) {
+        Some(Ok(base)) => Some(base),
+        Some(Err(message)) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
+    if opt.exact {
+        run(opt, Interpreter::<BigNum>::default(), base);
+    } else if opt.complex {
+        run(opt, Interpreter::<Complex>::default(), base);
+    } else if opt.rational {
+        run(opt, Interpreter::<Rational>::default(), base);
+    } else {
+        run(opt, Interpreter::<f64>::default(), base);
+    }
+}
 
+fn run<N: Num + Display>(opt: Opt, mut interpreter: Interpreter<N>, base: Option<u32>) {
     if let Some(expr) = opt.expr {
         match tokenize(&expr) {
             Ok(tokens) => match parse(&tokens) {
                 Ok(expr) => match interpreter.eval(&expr) {
                     Ok(result) => {
-                        println!("{}", result);
+                        println!("{}", render_eval_result(&expr, &result, base));
                         return;
                     }
                     Err(e) => eprintln!("{:?}", e),
@@ -93,6 +131,7 @@ fn main() {
                 opt.vars,
                 opt.no_color,
                 ":",
+                base,
             );
         }
     }
@@ -124,12 +163,14 @@ fn print_help(no_color: bool) {
     println!("\t|-9| + 3!");
     println!("\tx = abs(-5)");
     println!("\t-x^4");
+    println!("\tf(x) = x^2 + 1");
+    println!("\tf(3)");
 }
 
 fn get_variant_ord<N: Num>(v: &Variant<N>) -> usize {
     match v {
         Variant::Num(_) => 1,
-        Variant::Function(_) => 0,
+        Variant::Function(_, _) => 0,
     }
 }
 
@@ -151,8 +192,12 @@ fn print_vars<N: Num + Display>(interpreter: &Interpreter<N>, no_color: bool) {
                     n.clone()
                 )
             }
-            Variant::Function(_) => {
-                fmt = format!("{}(..)", if no_color { id.normal() } else { id.green() })
+            Variant::Function(param, _) => {
+                fmt = format!(
+                    "{}({})",
+                    if no_color { id.normal() } else { id.green() },
+                    param
+                )
             }
         }
         println!(
@@ -166,6 +211,76 @@ fn print_vars<N: Num + Display>(interpreter: &Interpreter<N>, no_color: bool) {
     }
 }
 
+const BASE_DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+fn validate_base(base: u32) -> Result<u32, String> {
+    if (2..=36).contains(&base) {
+        Ok(base)
+    } else {
+        Err(format!("Unknown base: {}", base))
+    }
+}
+
+/// Formats `value` in the given `base`, with up to 10 fractional digits.
+fn format_in_base(value: f64, base: u32) -> String {
+    let negative = value.is_sign_negative() && value != 0.0;
+    let value = value.abs();
+
+    let mut int_part = value.trunc() as u64;
+    let mut int_digits = Vec::new();
+    loop {
+        int_digits.push(BASE_DIGITS[(int_part % base as u64) as usize]);
+        int_part /= base as u64;
+        if int_part == 0 {
+            break;
+        }
+    }
+    int_digits.reverse();
+
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+    out.push_str(std::str::from_utf8(&int_digits).unwrap());
+
+    let mut frac_part = value.fract();
+    if frac_part > 0.0 {
+        out.push('.');
+        for _ in 0..10 {
+            if frac_part <= 0.0 {
+                break;
+            }
+            frac_part *= base as f64;
+            let digit = frac_part.trunc() as usize;
+            out.push(BASE_DIGITS[digit] as char);
+            frac_part -= frac_part.trunc();
+        }
+    }
+
+    out
+}
+
+/// Renders an evaluation result for display, reformatting it in `base` when
+/// one was requested and the result is representable as an `f64`.
+fn render_result<N: Display>(result: &N, base: Option<u32>) -> String {
+    match base {
+        Some(base) => match format!("{}", result).parse::<f64>() {
+            Ok(value) => format_in_base(value, base),
+            Err(_) => format!("{}", result),
+        },
+        None => format!("{}", result),
+    }
+}
+
+/// Like `render_result`, but prints a definition confirmation for
+/// `Expr::FunctionDef` instead of the misleading bare `0` it evaluates to.
+fn render_eval_result<N: Display>(expr: &Expr, result: &N, base: Option<u32>) -> String {
+    match expr {
+        Expr::FunctionDef(name, param, _) => format!("{}({}) defined", name, param),
+        _ => render_result(result, base),
+    }
+}
+
 fn format_error(span: Range<usize>, message: &str) -> String {
     format!(
         " {}{} {}",
@@ -183,6 +298,7 @@ fn evaluate<N: Num + Display>(
     bvars: bool,
     bno_color: bool,
     success_prefix: &str,
+    base: Option<u32>,
 ) {
     match tokenize(input) {
         Ok(tokens) => {
@@ -221,7 +337,7 @@ fn evaluate<N: Num + Display>(
                                 } else {
                                     success_prefix.green()
                                 },
-                                result
+                                render_eval_result(&expr, &result, base)
                             );
                         }
                         Err(err) => {
@@ -304,5 +420,17 @@ fn display_interpret_error(err: &InterpretError) -> String {
         InterpretError::FunctionNameUsedLikeVar(id) => {
             format!("The function {:?} cannot be used without arguments.", id)
         }
+        InterpretError::Math(err) => display_math_error(err),
+    }
+}
+
+fn display_math_error(err: &MathError) -> String {
+    match err {
+        MathError::DivideByZero => "Math Error: Divide by zero".to_owned(),
+        MathError::ModuloByZero => "Math Error: Modulo by zero".to_owned(),
+        MathError::DomainError(_) => "Domain Error: out of bounds".to_owned(),
+        MathError::FactorialOfNegative => {
+            "Domain Error: factorial of a negative integer".to_owned()
+        }
     }
 }
@@ -0,0 +1,245 @@
+//! Complex-number backend: `a + bi` arithmetic implementing `Num`, so the
+//! interpreter can solve things like `sqrt(-1)` and `(3+4i)^2`.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::Num;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    pub fn modulus(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn argument(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    fn conjugate(self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Self) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Self) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Self) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    /// Panics on division by zero; prefer `checked_div`.
+    fn div(self, other: Self) -> Complex {
+        self.checked_div(other).expect("division by zero")
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im == 0.0 {
+            write!(f, "{}", self.re)
+        } else if self.re == 0.0 {
+            write!(f, "{}i", self.im)
+        } else if self.im < 0.0 {
+            write!(f, "{} - {}i", self.re, -self.im)
+        } else {
+            write!(f, "{} + {}i", self.re, self.im)
+        }
+    }
+}
+
+impl Num for Complex {
+    fn from_f64(value: f64) -> Self {
+        Complex::new(value, 0.0)
+    }
+
+    fn checked_div(self, other: Self) -> Option<Self> {
+        let denom = other.re * other.re + other.im * other.im;
+        if denom == 0.0 {
+            return None;
+        }
+        let numerator = self * other.conjugate();
+        Some(Complex::new(numerator.re / denom, numerator.im / denom))
+    }
+
+    fn checked_rem(self, other: Self) -> Option<Self> {
+        if other.re == 0.0 {
+            None
+        } else {
+            Some(self.rem(other))
+        }
+    }
+
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+
+    fn rem(self, other: Self) -> Self {
+        Complex::new(self.re % other.re, 0.0)
+    }
+
+    /// Small integer exponents use exact repeated multiplication, so e.g.
+    /// `(3+4i)^2` comes out as `-7 + 24i` instead of a polar-form smear.
+    /// Larger or fractional exponents fall back to polar form
+    /// (`r^n * (cos n0 + i sin n0)`); a complex exponent falls back to just
+    /// its real part.
+    fn pow(self, exponent: Self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+            return Complex::new(0.0, 0.0);
+        }
+
+        if exponent.im == 0.0 && exponent.re.fract() == 0.0 && exponent.re.abs() <= 64.0 {
+            let n = exponent.re as i64;
+            let result = (0..n.unsigned_abs()).fold(Complex::new(1.0, 0.0), |acc, _| acc * self);
+            return if n < 0 {
+                Complex::new(1.0, 0.0)
+                    .checked_div(result)
+                    .unwrap_or(Complex::new(f64::NAN, f64::NAN))
+            } else {
+                result
+            };
+        }
+
+        let r = self.modulus().powf(exponent.re);
+        let theta = self.argument() * exponent.re;
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// `Err` for a zero base raised to a negative exponent (e.g. `0^-1`),
+    /// which `pow` would otherwise silently answer `0` for.
+    fn checked_pow(self, exponent: Self) -> Result<Self, crate::MathError> {
+        if self.re == 0.0 && self.im == 0.0 && exponent.im == 0.0 && exponent.re < 0.0 {
+            return Err(crate::MathError::DivideByZero);
+        }
+        Ok(self.pow(exponent))
+    }
+
+    /// The principal square root, so negative reals no longer produce `NaN`.
+    fn sqrt(self) -> Self {
+        if self.im == 0.0 && self.re < 0.0 {
+            return Complex::new(0.0, (-self.re).sqrt());
+        }
+
+        let r = self.modulus().sqrt();
+        let theta = self.argument() / 2.0;
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    fn sin(self) -> Self {
+        Complex::new(
+            self.re.sin() * self.im.cosh(),
+            self.re.cos() * self.im.sinh(),
+        )
+    }
+
+    fn cos(self) -> Self {
+        Complex::new(
+            self.re.cos() * self.im.cosh(),
+            -self.re.sin() * self.im.sinh(),
+        )
+    }
+
+    fn tan(self) -> Self {
+        self.sin()
+            .checked_div(self.cos())
+            .unwrap_or(Complex::new(f64::NAN, f64::NAN))
+    }
+
+    fn log10(self) -> Self {
+        let r = self.modulus();
+        let theta = self.argument();
+        Complex::new(
+            r.ln() / std::f64::consts::LN_10,
+            theta / std::f64::consts::LN_10,
+        )
+    }
+
+    /// The modulus, as a real value.
+    fn abs(self) -> Self {
+        Complex::new(self.modulus(), 0.0)
+    }
+
+    fn i() -> Self {
+        Complex::new(0.0, 1.0)
+    }
+
+    /// Falls back to the real part only, like `rem`.
+    fn factorial(self) -> Self {
+        Complex::new(crate::computer::factorial_approx(self.re), 0.0)
+    }
+
+    fn checked_factorial(self) -> Result<Self, crate::MathError> {
+        if crate::computer::is_factorial_pole(self.re) {
+            Err(crate::MathError::FactorialOfNegative)
+        } else {
+            Ok(self.factorial())
+        }
+    }
+
+    /// Complex numbers have no natural total order, so `min`/`max` compare
+    /// by modulus instead.
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        self.modulus()
+            .partial_cmp(&other.modulus())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_integer_exponents_are_exact() {
+        let result = Complex::new(3.0, 4.0).pow(Complex::new(2.0, 0.0));
+        assert_eq!(result, Complex::new(-7.0, 24.0));
+    }
+
+    #[test]
+    fn i_squared_is_exactly_negative_one() {
+        let result = Complex::i().pow(Complex::new(2.0, 0.0));
+        assert_eq!(result, Complex::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn checked_pow_rejects_zero_to_a_negative_exponent() {
+        let zero = Complex::new(0.0, 0.0);
+        let neg_one = Complex::new(-1.0, 0.0);
+        assert_eq!(
+            Num::checked_pow(zero, neg_one),
+            Err(crate::MathError::DivideByZero)
+        );
+    }
+}
@@ -1,44 +1,89 @@
-//! For taking the product of the parser and calculating it into a 
-//! a final form. In this case, the final form is an f64.
-
-use crate::lexer::*;
-use crate::parser::*;
-
-// If you come bearing big changes, you may have to rewrite
-// this to suit your needs.
-
-/// Turn an AST / Expr into an f64.
-pub fn compute(expr: &Expr) -> f64 {
-    match expr {
-        Expr::Constant(num) => *num,
-        Expr::Identifier(_) => 0.,
-        Expr::Neg(expr) => -compute(expr),
-        Expr::BinOp(op, lexpr, rexpr) => {
-            let lnum = compute(&lexpr);
-            let rnum = compute(&rexpr);
-
-            match op {
-                Operator::Plus => lnum + rnum,
-                Operator::Minus => lnum - rnum,
-                Operator::Star => lnum * rnum,
-                Operator::Slash => lnum / rnum,
-                Operator::Percent => lnum % rnum,
-                _ => unimplemented!(),
-            }
-        }
-        Expr::Function(function, expr) => {
-            let num = compute(&expr);
-            match function {
-                Function::Sqrt => num.sqrt(),
-                Function::Sin => num.sin(),
-                Function::Cos => num.cos(),
-                Function::Tan => num.tan(),
-                Function::Log => num.log10(),
-                Function::Abs => num.abs(),
-            }
-        }
-        Expr::Pow(lexpr, rexpr) => {
-            compute(&lexpr).powf(compute(&rexpr))
+//! Math helpers (`gamma`, pole detection, `MathError`) shared by the `Num`
+//! implementations that `Interpreter` evaluates an `Expr` over.
+
+use crate::lexer::Function;
+
+/// A computation that is well-defined mathematically but has no finite
+/// real answer, such as dividing by zero or taking `log` of a negative
+/// number. Replaces the `inf`/`NaN` a naive `f64` evaluator would otherwise
+/// leak silently.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MathError {
+    DivideByZero,
+    ModuloByZero,
+    DomainError(Function),
+    /// `!` of a negative integer: a pole of the gamma function. Not a
+    /// `DomainError(Function)` since factorial isn't a callable `Function`.
+    FactorialOfNegative,
+}
+
+/// True when `num` is an odd multiple of pi/2, i.e. where `tan` has a pole.
+pub(crate) fn is_tan_pole(num: f64) -> bool {
+    let halves = num / (std::f64::consts::PI / 2.0);
+    (halves - halves.round()).abs() < 1e-9 && halves.round() as i64 % 2 != 0
+}
+
+/// The Lanczos approximation of the gamma function, used to extend `!` to
+/// non-integer arguments (e.g. `2.5!`).
+pub(crate) fn gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula, for the poles at the non-positive integers.
+        std::f64::consts::PI / ((std::f64::consts::PI * x).sin() * gamma(1.0 - x))
+    } else {
+        let x = x - 1.0;
+        let mut a = COEFFICIENTS[0];
+        let t = x + G + 0.5;
+        for (i, coefficient) in COEFFICIENTS.iter().enumerate().skip(1) {
+            a += coefficient / (x + i as f64);
         }
+        (2.0 * std::f64::consts::PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * a
+    }
+}
+
+/// True when `num` is a negative integer, i.e. a pole of the gamma function
+/// used to compute factorials.
+pub(crate) fn is_factorial_pole(num: f64) -> bool {
+    num < 0.0 && num.fract() == 0.0
+}
+
+/// The factorial of `num`: an exact iterative product for non-negative
+/// integers, falling back to `gamma(num + 1)` for fractional arguments like
+/// `2.5!` (`gamma` alone is close enough to be off by a floating-point ulp,
+/// e.g. `3! = 6.00000000000000711`).
+pub(crate) fn factorial_approx(num: f64) -> f64 {
+    if num >= 0.0 && num.fract() == 0.0 && num <= u32::MAX as f64 {
+        (1..=num as u64).fold(1.0, |acc, n| acc * n as f64)
+    } else {
+        gamma(num + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factorial_of_small_integers_is_exact() {
+        assert_eq!(factorial_approx(3.0), 6.0);
+        assert_eq!(factorial_approx(5.0), 120.0);
+    }
+
+    #[test]
+    fn factorial_of_negative_integer_is_a_pole() {
+        assert!(is_factorial_pole(-1.0));
+        assert!(!is_factorial_pole(0.0));
     }
 }
@@ -12,11 +12,15 @@ pub enum Operator {
     RParen,
     Pipe,
     Equals,
+    Comma,
+    /// Postfix factorial, e.g. `3!`.
+    Bang,
 }
 use self::Operator::*;
 
-/// All functions assume the next factor immediately following to be their argument.
-/// Functions cannot contain more than a single argument. This may be changed in the future.
+/// Functions take a comma-separated argument list in parentheses, e.g.
+/// `log(2, 8)`. Most only accept one argument; `Function::arity` gives the
+/// allowed range for each.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Function {
     // mul 2,3
@@ -24,21 +28,61 @@ pub enum Function {
     Sin,
     Cos,
     Tan,
+    /// One argument computes `log10`; two compute `log(base, x)`.
     Log,
     Abs,
+    /// `root(n, x)`: the nth root of `x`.
+    Root,
+    /// The smallest of any number of arguments.
+    Min,
+    /// The largest of any number of arguments.
+    Max,
 }
 use self::Function::*;
 
+impl Function {
+    /// The name used to refer to this function in arity error messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sqrt => "sqrt",
+            Sin => "sin",
+            Cos => "cos",
+            Tan => "tan",
+            Log => "log",
+            Abs => "abs",
+            Root => "root",
+            Min => "min",
+            Max => "max",
+        }
+    }
+
+    /// `(minimum, maximum)` number of arguments this function accepts.
+    /// `None` for the maximum means there is no upper bound.
+    pub fn arity(&self) -> (usize, Option<usize>) {
+        match self {
+            Sqrt | Sin | Cos | Tan | Abs => (1, Some(1)),
+            Log => (1, Some(2)),
+            Root => (2, Some(2)),
+            Min | Max => (1, None),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Constant {
     Pi,
-    E
+    E,
+    /// The imaginary unit, for use with the `Complex` backend.
+    I,
 }
 use self::Constant::*;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
-    Number(f64),
+    /// The literal's exact decimal text (e.g. `"10000000000000001"`), kept
+    /// as written instead of an `f64` so a backend like `BigNum` can parse
+    /// it exactly instead of inheriting `f64`'s ~15-17 significant digits.
+    Number(String),
     Operator(Operator),
     Function(Function),
     Constant(Constant),
@@ -52,6 +96,38 @@ pub enum Token {
 pub enum LexerError {
     InvalidCharacter(char),
     InvalidNumber(String),
+    /// A digit was found that is out of range for the base it was scanned in,
+    /// e.g. `2` in a binary literal or `g` in a hexadecimal one.
+    InvalidDigitForBase(char, u32),
+}
+
+/// Scans a run of digits valid for `base` (using `0`-`9` then `a`-`z` for
+/// higher radixes) starting at `chars[*i]`, advancing `i` past them and
+/// folding the result into an `f64`.
+fn scan_digits_in_base(chars: &[char], i: &mut usize, base: u32) -> Result<f64, LexerError> {
+    let mut value = 0f64;
+    let mut any_digits = false;
+
+    while *i < chars.len() {
+        let c = chars[*i];
+        match c.to_digit(36) {
+            Some(d) if d < base => {
+                value = value * base as f64 + d as f64;
+                any_digits = true;
+                *i += 1;
+            }
+            Some(_) if c.is_alphanumeric() => {
+                return Err(LexerError::InvalidDigitForBase(c, base));
+            }
+            _ => break,
+        }
+    }
+
+    if !any_digits {
+        return Err(LexerError::InvalidNumber(String::new()));
+    }
+
+    Ok(value)
 }
 
 /// Turn a string into a vector of tokens. This function generally takes the most time,
@@ -59,7 +135,7 @@ pub enum LexerError {
 /// reasonably possible.
 /// ```
 /// let tokens = tokenize("2 + 2").unwrap();
-/// assert_eq!(tokens.as_slice(), &[Token::Number(2.0), Token::Operator(Operator::Plus), Token::Number(2.0)]);
+/// assert_eq!(tokens.as_slice(), &[Token::Number("2".to_owned()), Token::Operator(Operator::Plus), Token::Number("2".to_owned())]);
 /// ```
 pub fn tokenize(input: &str) -> Result<Vec<Token>, LexerError> {
     let mut tokens = Vec::<Token>::new();
@@ -79,21 +155,57 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, LexerError> {
             ')' => tokens.push(Token::Operator(RParen)),
             '|' => tokens.push(Token::Operator(Pipe)),
             '=' => tokens.push(Token::Operator(Equals)),
+            ',' => tokens.push(Token::Operator(Comma)),
+            '!' => tokens.push(Token::Operator(Bang)),
             c => {
                 if c.is_whitespace() {
                     i += 1;
                     continue;
-                } else if c.is_digit(10) || c == '.' {
+                } else if c.is_ascii_digit() || c == '.' {
+                    // `0x`/`0b`/`0o` prefixed literals.
+                    if c == '0' && i + 1 < chars.len() {
+                        let base = match chars[i + 1] {
+                            'x' | 'X' => Some(16),
+                            'b' | 'B' => Some(2),
+                            'o' | 'O' => Some(8),
+                            _ => None,
+                        };
+
+                        if let Some(base) = base {
+                            i += 2;
+                            let num = scan_digits_in_base(&chars, &mut i, base)?;
+                            tokens.push(Token::Number(num.to_string()));
+                            continue;
+                        }
+                    }
+
                     let mut number_string = c.to_string(); // Like creating a new string and pushing the character.
-                    
+
                     i += 1;
-                    while i < chars.len() && (chars[i].is_digit(10) || chars[i] == '.') {
+                    while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
                         number_string.push(chars[i]);
                         i += 1;
                     }
 
+                    // `<base>#<digits>` literals, e.g. `16#1A`.
+                    if i < chars.len() && chars[i] == '#' {
+                        let base: u32 = number_string
+                            .parse()
+                            .ok()
+                            .filter(|b| (2..=36).contains(b))
+                            .ok_or(LexerError::InvalidNumber(number_string))?;
+
+                        i += 1; // Step over the '#'.
+                        let num = scan_digits_in_base(&chars, &mut i, base)?;
+                        tokens.push(Token::Number(num.to_string()));
+                        continue;
+                    }
+
+                    // Just validated, not parsed: the original text is kept
+                    // verbatim so an exact backend like `BigNum` can parse it
+                    // without first losing precision through `f64`.
                     match number_string.parse::<f64>() {
-                        Ok(num) => tokens.push(Token::Number(num)),
+                        Ok(_) => tokens.push(Token::Number(number_string)),
                         _ => return Err(LexerError::InvalidNumber(number_string)),
                     }
 
@@ -112,6 +224,7 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, LexerError> {
                         // Constants
                         "pi" => tokens.push(Token::Constant(Pi)),
                         "e" => tokens.push(Token::Constant(E)),
+                        "i" => tokens.push(Token::Constant(I)),
 
                         // Functions
                         "sqrt" => tokens.push(Token::Function(Sqrt)),
@@ -120,7 +233,11 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, LexerError> {
                         "tan" => tokens.push(Token::Function(Tan)),
                         "log" => tokens.push(Token::Function(Log)),
                         "abs" => tokens.push(Token::Function(Abs)),
-                        
+                        "root" => tokens.push(Token::Function(Root)),
+                        "min" => tokens.push(Token::Function(Min)),
+                        "max" => tokens.push(Token::Function(Max)),
+
+
                         id => tokens.push(Token::Identifier(id.to_owned())),
                     }
 
@@ -0,0 +1,486 @@
+//! RSCALC is split into a `lexer` (text -> `Token`s), a `parser`
+//! (`Token`s -> `Expr`), and `Interpreter`: a stateful, generic evaluator
+//! that can run an `Expr` over any type implementing `Num` (`f64`, `BigNum`,
+//! `Complex`, ...). `computer` holds the math helpers (`gamma`, `MathError`,
+//! ...) shared by those `Num` implementations.
+
+pub mod bignum;
+pub mod complex;
+pub mod computer;
+pub mod lexer;
+pub mod parser;
+pub mod rational;
+
+pub use bignum::BigNum;
+pub use complex::Complex;
+pub use computer::MathError;
+pub use lexer::{Constant, Function, LexerError, Operator, Token};
+pub use parser::{Expr, ParseErrorCode};
+pub use rational::Rational;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Range, Sub};
+
+/// A numeric domain an `Interpreter` can evaluate expressions over.
+pub trait Num:
+    Clone
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Sized
+{
+    fn from_f64(value: f64) -> Self;
+    /// Parses a numeric literal's exact decimal text. Backends with an exact
+    /// decimal representation (like `BigNum`) should parse `s` directly
+    /// instead of using the default, which round-trips through `f64` and so
+    /// loses precision beyond `f64`'s ~15-17 significant digits.
+    fn from_str_exact(s: &str) -> Self {
+        Self::from_f64(s.parse().unwrap_or(0.0))
+    }
+    /// `None` on division by zero, instead of panicking or producing `NaN`.
+    fn checked_div(self, other: Self) -> Option<Self>;
+    /// `None` on modulo by zero, instead of panicking or producing `NaN`.
+    fn checked_rem(self, other: Self) -> Option<Self>;
+    fn neg(self) -> Self;
+    fn rem(self, other: Self) -> Self;
+    fn pow(self, exponent: Self) -> Self;
+    /// `Err` when the exponent has no finite real answer for this base,
+    /// e.g. a zero base raised to a negative exponent (`0^-1`). Backends
+    /// without that notion can leave this at its always-succeeds default.
+    fn checked_pow(self, exponent: Self) -> Result<Self, MathError> {
+        Ok(self.pow(exponent))
+    }
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn log10(self) -> Self;
+    fn abs(self) -> Self;
+    /// The imaginary unit. Backends with no notion of one (`f64`, `BigNum`)
+    /// return their closest stand-in rather than refusing to build.
+    fn i() -> Self;
+    /// The factorial, via the gamma function (`Γ(n+1)`) so fractional
+    /// arguments like `2.5!` work.
+    fn factorial(self) -> Self;
+
+    /// `Err` with the offending function when there is no real answer
+    /// (e.g. `sqrt` of a negative). Backends without that notion (like
+    /// `Complex`) can leave this at its always-succeeds default.
+    fn checked_sqrt(self) -> Result<Self, Function> {
+        Ok(self.sqrt())
+    }
+    fn checked_log10(self) -> Result<Self, Function> {
+        Ok(self.log10())
+    }
+    fn checked_tan(self) -> Result<Self, Function> {
+        Ok(self.tan())
+    }
+    /// `Err` when `self` is a negative integer: a pole of the gamma
+    /// function `factorial` is computed from.
+    fn checked_factorial(self) -> Result<Self, MathError> {
+        Ok(self.factorial())
+    }
+
+    /// Orders two values for `min`/`max`. Backends with no natural ordering
+    /// (like `Complex`) should compare by magnitude instead.
+    fn compare(&self, other: &Self) -> std::cmp::Ordering;
+}
+
+impl Num for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn checked_div(self, other: Self) -> Option<Self> {
+        if other == 0.0 {
+            None
+        } else {
+            Some(self / other)
+        }
+    }
+
+    fn checked_rem(self, other: Self) -> Option<Self> {
+        if other == 0.0 {
+            None
+        } else {
+            Some(self % other)
+        }
+    }
+
+    fn neg(self) -> Self {
+        -self
+    }
+
+    fn rem(self, other: Self) -> Self {
+        self % other
+    }
+
+    fn pow(self, exponent: Self) -> Self {
+        self.powf(exponent)
+    }
+
+    fn checked_pow(self, exponent: Self) -> Result<Self, MathError> {
+        if self == 0.0 && exponent < 0.0 {
+            Err(MathError::DivideByZero)
+        } else {
+            Ok(self.powf(exponent))
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f64::tan(self)
+    }
+
+    fn log10(self) -> Self {
+        f64::log10(self)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn i() -> Self {
+        f64::NAN
+    }
+
+    fn factorial(self) -> Self {
+        computer::factorial_approx(self)
+    }
+
+    fn checked_sqrt(self) -> Result<Self, Function> {
+        if self < 0.0 {
+            Err(Function::Sqrt)
+        } else {
+            Ok(self.sqrt())
+        }
+    }
+
+    fn checked_log10(self) -> Result<Self, Function> {
+        if self <= 0.0 {
+            Err(Function::Log)
+        } else {
+            Ok(self.log10())
+        }
+    }
+
+    fn checked_tan(self) -> Result<Self, Function> {
+        if computer::is_tan_pole(self) {
+            Err(Function::Tan)
+        } else {
+            Ok(self.tan())
+        }
+    }
+
+    fn checked_factorial(self) -> Result<Self, MathError> {
+        if computer::is_factorial_pole(self) {
+            Err(MathError::FactorialOfNegative)
+        } else {
+            Ok(self.factorial())
+        }
+    }
+
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        self.partial_cmp(other).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expr: Expr) -> Result<f64, InterpretError> {
+        Interpreter::<f64>::default().eval(&expr)
+    }
+
+    fn constant(n: f64) -> Expr {
+        Expr::Constant(n.to_string())
+    }
+
+    #[test]
+    fn two_arg_log_rejects_non_positive_operands() {
+        let log = |base: f64, x: f64| {
+            eval(Expr::Function(
+                Function::Log,
+                vec![constant(base), constant(x)],
+            ))
+        };
+        assert_eq!(
+            log(2.0, -8.0),
+            Err(InterpretError::Math(MathError::DomainError(Function::Log)))
+        );
+        assert_eq!(
+            log(-2.0, 8.0),
+            Err(InterpretError::Math(MathError::DomainError(Function::Log)))
+        );
+        assert_eq!(log(2.0, 8.0), Ok(3.0));
+    }
+
+    #[test]
+    fn root_allows_odd_degree_of_negative_radicand() {
+        let root = |n: f64, x: f64| {
+            eval(Expr::Function(
+                Function::Root,
+                vec![constant(n), constant(x)],
+            ))
+        };
+        assert_eq!(root(3.0, -8.0), Ok(-2.0));
+        assert_eq!(root(3.0, 8.0), Ok(2.0));
+    }
+
+    #[test]
+    fn root_rejects_even_degree_of_negative_radicand() {
+        let root = |n: f64, x: f64| {
+            eval(Expr::Function(
+                Function::Root,
+                vec![constant(n), constant(x)],
+            ))
+        };
+        assert_eq!(
+            root(2.0, -8.0),
+            Err(InterpretError::Math(MathError::DomainError(Function::Root)))
+        );
+    }
+
+    #[test]
+    fn constant_text_evaluates_to_its_value() {
+        assert_eq!(eval(constant(2.5)), Ok(2.5));
+    }
+}
+
+/// True when `n` is exactly an odd integer, checked via `n % 2 == ±1` so it
+/// works across every `Num` backend without a float conversion. Used by
+/// `Function::Root` to allow real odd-degree roots of negative radicands.
+fn is_odd_integer<N: Num>(n: &N) -> bool {
+    match n.clone().checked_rem(N::from_f64(2.0)) {
+        Some(r) => {
+            r.compare(&N::from_f64(1.0)) == std::cmp::Ordering::Equal
+                || r.compare(&N::from_f64(-1.0)) == std::cmp::Ordering::Equal
+        }
+        None => false,
+    }
+}
+
+/// Something a variable name can be bound to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Variant<N> {
+    Num(N),
+    /// A user-defined function: its parameter name and body `Expr`.
+    Function(String, Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpretError {
+    TooFewArgs(String, usize),
+    TooManyArgs(String, usize),
+    VarDoesNotExist(String),
+    VarIsNotFunction(String),
+    FunctionNameUsedLikeVar(String),
+    Math(MathError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenizeError {
+    pub code: LexerError,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub code: ParseErrorCode,
+    pub span: Range<usize>,
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>, TokenizeError> {
+    lexer::tokenize(input).map_err(|code| TokenizeError {
+        code,
+        span: 0..input.len(),
+    })
+}
+
+pub fn parse(tokens: &[Token]) -> Result<Expr, ParseError> {
+    parser::parse(tokens).map_err(|code| ParseError {
+        code,
+        span: 0..tokens.len(),
+    })
+}
+
+/// Stateful evaluator: holds the variable/function environment between
+/// calls to `eval` so a REPL session can build on earlier expressions.
+pub struct Interpreter<N> {
+    pub vars: HashMap<String, Variant<N>>,
+}
+
+impl<N> Default for Interpreter<N> {
+    fn default() -> Self {
+        Interpreter {
+            vars: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Num> Interpreter<N> {
+    pub fn eval(&mut self, expr: &Expr) -> Result<N, InterpretError> {
+        match expr {
+            Expr::Constant(n) => Ok(N::from_str_exact(n)),
+            Expr::Imaginary => Ok(N::i()),
+            Expr::Identifier(id) => match self.vars.get(id) {
+                Some(Variant::Num(n)) => Ok(n.clone()),
+                Some(Variant::Function(_, _)) => {
+                    Err(InterpretError::FunctionNameUsedLikeVar(id.clone()))
+                }
+                None => Err(InterpretError::VarDoesNotExist(id.clone())),
+            },
+            Expr::Assign(id, value) => {
+                let result = self.eval(value)?;
+                self.vars
+                    .insert(id.clone(), Variant::Num(result.clone()));
+                Ok(result)
+            }
+            Expr::Neg(inner) => Ok(self.eval(inner)?.neg()),
+            Expr::BinOp(op, lhs, rhs) => {
+                let l = self.eval(lhs)?;
+                let r = self.eval(rhs)?;
+                Ok(match op {
+                    Operator::Plus => l + r,
+                    Operator::Minus => l - r,
+                    Operator::Star => l * r,
+                    Operator::Slash => l
+                        .checked_div(r)
+                        .ok_or(InterpretError::Math(MathError::DivideByZero))?,
+                    Operator::Percent => l
+                        .checked_rem(r)
+                        .ok_or(InterpretError::Math(MathError::ModuloByZero))?,
+                    _ => unreachable!("binary operator produced by the parser"),
+                })
+            }
+            Expr::Function(function, arg_exprs) => {
+                let (min_args, max_args) = function.arity();
+                if arg_exprs.len() < min_args {
+                    return Err(InterpretError::TooFewArgs(
+                        function.name().to_owned(),
+                        min_args,
+                    ));
+                }
+                if let Some(max_args) = max_args {
+                    if arg_exprs.len() > max_args {
+                        return Err(InterpretError::TooManyArgs(
+                            function.name().to_owned(),
+                            max_args,
+                        ));
+                    }
+                }
+
+                let mut args = Vec::with_capacity(arg_exprs.len());
+                for arg in arg_exprs {
+                    args.push(self.eval(arg)?);
+                }
+
+                Ok(match function {
+                    Function::Sqrt => args[0].clone().checked_sqrt().map_err(|f| {
+                        InterpretError::Math(MathError::DomainError(f))
+                    })?,
+                    Function::Sin => args[0].clone().sin(),
+                    Function::Cos => args[0].clone().cos(),
+                    Function::Tan => args[0].clone().checked_tan().map_err(|f| {
+                        InterpretError::Math(MathError::DomainError(f))
+                    })?,
+                    Function::Log => {
+                        if args.len() == 2 {
+                            // log(base, x) = x.log10() / base.log10(); both
+                            // operands are domain-checked before dividing,
+                            // so a non-positive base or x surfaces a
+                            // `DomainError` instead of silently yielding NaN.
+                            let base = args[0].clone().checked_log10().map_err(|f| {
+                                InterpretError::Math(MathError::DomainError(f))
+                            })?;
+                            let x = args[1].clone().checked_log10().map_err(|f| {
+                                InterpretError::Math(MathError::DomainError(f))
+                            })?;
+                            x.checked_div(base)
+                                .ok_or(InterpretError::Math(MathError::DivideByZero))?
+                        } else {
+                            args[0].clone().checked_log10().map_err(|f| {
+                                InterpretError::Math(MathError::DomainError(f))
+                            })?
+                        }
+                    }
+                    Function::Abs => args[0].clone().abs(),
+                    Function::Root => {
+                        let n = args[0].clone();
+                        let x = args[1].clone();
+                        let negative = x.compare(&N::from_f64(0.0)) == std::cmp::Ordering::Less;
+                        // Odd-degree real roots of a negative radicand are
+                        // well-defined (e.g. root(3, -8) = -2); only reject
+                        // when there's no real answer, i.e. an even degree.
+                        if negative && !is_odd_integer(&n) {
+                            return Err(InterpretError::Math(MathError::DomainError(
+                                Function::Root,
+                            )));
+                        }
+                        let exponent = N::from_f64(1.0).checked_div(n).ok_or(
+                            InterpretError::Math(MathError::DivideByZero),
+                        )?;
+                        if negative {
+                            x.neg().pow(exponent).neg()
+                        } else {
+                            x.pow(exponent)
+                        }
+                    }
+                    Function::Min => args
+                        .into_iter()
+                        .reduce(|a, b| if b.compare(&a) == std::cmp::Ordering::Less { b } else { a })
+                        .expect("arity guarantees at least one argument"),
+                    Function::Max => args
+                        .into_iter()
+                        .reduce(|a, b| if b.compare(&a) == std::cmp::Ordering::Greater { b } else { a })
+                        .expect("arity guarantees at least one argument"),
+                })
+            }
+            Expr::Pow(base, exponent) => {
+                let b = self.eval(base)?;
+                let e = self.eval(exponent)?;
+                b.checked_pow(e).map_err(InterpretError::Math)
+            }
+            Expr::FunctionDef(name, param, body) => {
+                self.vars.insert(
+                    name.clone(),
+                    Variant::Function(param.clone(), (**body).clone()),
+                );
+                // No single numeric result represents "a function was
+                // defined"; `main` special-cases this expression to print a
+                // confirmation instead of a misleading bare `0`.
+                Ok(N::from_f64(0.0))
+            }
+            Expr::Call(name, arg) => match self.vars.get(name).cloned() {
+                Some(Variant::Function(param, body)) => {
+                    let substituted = body.replace(&param, arg);
+                    self.eval(&substituted)
+                }
+                Some(Variant::Num(_)) => Err(InterpretError::VarIsNotFunction(name.clone())),
+                None => Err(InterpretError::VarDoesNotExist(name.clone())),
+            },
+            Expr::Postfix(op, inner) => {
+                let n = self.eval(inner)?;
+                Ok(match op {
+                    Operator::Bang => n.checked_factorial().map_err(InterpretError::Math)?,
+                    _ => unreachable!("postfix operator produced by the parser"),
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Turns a token stream into an abstract syntax tree (`Expr`) that
+//! `Interpreter::eval` can walk.
+
+use crate::lexer::{Constant, Function, Operator, Token};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A numeric literal, kept as its exact decimal text rather than an
+    /// `f64` so an exact backend like `BigNum` can parse it without loss.
+    Constant(String),
+    /// The imaginary unit `i`. Real-only backends have no value for this;
+    /// only a `Num` backend that knows what to do with it (e.g. `Complex`)
+    /// should be asked to evaluate an expression containing it.
+    Imaginary,
+    Identifier(String),
+    Assign(String, Box<Expr>),
+    Neg(Box<Expr>),
+    BinOp(Operator, Box<Expr>, Box<Expr>),
+    /// A function call with its (possibly multiple) arguments, e.g.
+    /// `log(2, 8)` or the single-argument `sqrt(4)`.
+    Function(Function, Vec<Expr>),
+    Pow(Box<Expr>, Box<Expr>),
+    /// A user-defined function binding, e.g. `f(x) = x^2 + 1`: the bound
+    /// parameter name and the body `Expr` it is substituted into.
+    FunctionDef(String, String, Box<Expr>),
+    /// A call to a user-defined function, e.g. `f(3)`.
+    Call(String, Box<Expr>),
+    /// A postfix operator, e.g. `3!`. Factorial (`Operator::Bang`) is
+    /// currently the only one.
+    Postfix(Operator, Box<Expr>),
+}
+
+impl Expr {
+    /// Substitutes every `Identifier(name)` node in this expression with
+    /// `value`, per the substitution `Token::Identifier`'s docs promise.
+    /// Used to bind a user-defined function's parameter at call time.
+    pub fn replace(&self, name: &str, value: &Expr) -> Expr {
+        match self {
+            Expr::Constant(n) => Expr::Constant(n.clone()),
+            Expr::Imaginary => Expr::Imaginary,
+            Expr::Identifier(id) => {
+                if id == name {
+                    value.clone()
+                } else {
+                    Expr::Identifier(id.clone())
+                }
+            }
+            Expr::Assign(id, inner) => Expr::Assign(id.clone(), Box::new(inner.replace(name, value))),
+            Expr::Neg(inner) => Expr::Neg(Box::new(inner.replace(name, value))),
+            Expr::BinOp(op, lhs, rhs) => Expr::BinOp(
+                *op,
+                Box::new(lhs.replace(name, value)),
+                Box::new(rhs.replace(name, value)),
+            ),
+            Expr::Function(function, args) => Expr::Function(
+                *function,
+                args.iter().map(|arg| arg.replace(name, value)).collect(),
+            ),
+            Expr::Pow(base, exponent) => Expr::Pow(
+                Box::new(base.replace(name, value)),
+                Box::new(exponent.replace(name, value)),
+            ),
+            Expr::FunctionDef(fn_name, param, body) => Expr::FunctionDef(
+                fn_name.clone(),
+                param.clone(),
+                Box::new(body.replace(name, value)),
+            ),
+            Expr::Call(fn_name, arg) => {
+                Expr::Call(fn_name.clone(), Box::new(arg.replace(name, value)))
+            }
+            Expr::Postfix(op, inner) => Expr::Postfix(*op, Box::new(inner.replace(name, value))),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseErrorCode {
+    UnexpectedEOF,
+    UnexpectedToken,
+    ExpectedClosingParen,
+    ExpectedClosingPipe,
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&Token> {
+        let tok = self.tokens.get(self.pos);
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ParseErrorCode> {
+        if let Some(Token::Identifier(name)) = self.peek() {
+            let name = name.clone();
+
+            // A function definition: `name(param) = body`.
+            if let (
+                Some(Token::Operator(Operator::LParen)),
+                Some(Token::Identifier(param)),
+                Some(Token::Operator(Operator::RParen)),
+                Some(Token::Operator(Operator::Equals)),
+            ) = (
+                self.tokens.get(self.pos + 1),
+                self.tokens.get(self.pos + 2),
+                self.tokens.get(self.pos + 3),
+                self.tokens.get(self.pos + 4),
+            ) {
+                let param = param.clone();
+                self.pos += 5;
+                let body = self.parse_additive()?;
+                return Ok(Expr::FunctionDef(name, param, Box::new(body)));
+            }
+
+            if let Some(Token::Operator(Operator::Equals)) = self.tokens.get(self.pos + 1) {
+                self.pos += 2;
+                let value = self.parse_additive()?;
+                return Ok(Expr::Assign(name, Box::new(value)));
+            }
+        }
+
+        self.parse_additive()
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ParseErrorCode> {
+        let mut expr = self.parse_multiplicative()?;
+        loop {
+            match self.peek() {
+                Some(Token::Operator(Operator::Plus)) => {
+                    self.pos += 1;
+                    let rhs = self.parse_multiplicative()?;
+                    expr = Expr::BinOp(Operator::Plus, Box::new(expr), Box::new(rhs));
+                }
+                Some(Token::Operator(Operator::Minus)) => {
+                    self.pos += 1;
+                    let rhs = self.parse_multiplicative()?;
+                    expr = Expr::BinOp(Operator::Minus, Box::new(expr), Box::new(rhs));
+                }
+                _ => break,
+            }
+        }
+        Ok(expr)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ParseErrorCode> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Operator(Operator::Star)) => {
+                    self.pos += 1;
+                    let rhs = self.parse_unary()?;
+                    expr = Expr::BinOp(Operator::Star, Box::new(expr), Box::new(rhs));
+                }
+                Some(Token::Operator(Operator::Slash)) => {
+                    self.pos += 1;
+                    let rhs = self.parse_unary()?;
+                    expr = Expr::BinOp(Operator::Slash, Box::new(expr), Box::new(rhs));
+                }
+                Some(Token::Operator(Operator::Percent)) => {
+                    self.pos += 1;
+                    let rhs = self.parse_unary()?;
+                    expr = Expr::BinOp(Operator::Percent, Box::new(expr), Box::new(rhs));
+                }
+                // Implicit multiplication, e.g. `12.3(0.7)` or `2 pi`.
+                Some(Token::Operator(Operator::LParen))
+                | Some(Token::Number(_))
+                | Some(Token::Constant(_))
+                | Some(Token::Function(_)) => {
+                    let rhs = self.parse_unary()?;
+                    expr = Expr::BinOp(Operator::Star, Box::new(expr), Box::new(rhs));
+                }
+                _ => break,
+            }
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseErrorCode> {
+        if let Some(Token::Operator(Operator::Minus)) = self.peek() {
+            self.pos += 1;
+            let expr = self.parse_unary()?;
+            return Ok(Expr::Neg(Box::new(expr)));
+        }
+        self.parse_power()
+    }
+
+    fn parse_power(&mut self) -> Result<Expr, ParseErrorCode> {
+        let base = self.parse_postfix()?;
+        if let Some(Token::Operator(Operator::Caret)) = self.peek() {
+            self.pos += 1;
+            let exponent = self.parse_unary()?; // Right-associative.
+            return Ok(Expr::Pow(Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    /// Binds tighter than `^`, e.g. `2^3!` is `2^(3!)`.
+    fn parse_postfix(&mut self) -> Result<Expr, ParseErrorCode> {
+        let mut expr = self.parse_primary()?;
+        while let Some(Token::Operator(Operator::Bang)) = self.peek() {
+            self.pos += 1;
+            expr = Expr::Postfix(Operator::Bang, Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseErrorCode> {
+        match self.bump().cloned() {
+            Some(Token::Number(n)) => Ok(Expr::Constant(n)),
+            Some(Token::Constant(Constant::Pi)) => {
+                Ok(Expr::Constant(std::f64::consts::PI.to_string()))
+            }
+            Some(Token::Constant(Constant::E)) => {
+                Ok(Expr::Constant(std::f64::consts::E.to_string()))
+            }
+            Some(Token::Constant(Constant::I)) => Ok(Expr::Imaginary),
+            Some(Token::Identifier(name)) => {
+                if let Some(Token::Operator(Operator::LParen)) = self.peek() {
+                    self.pos += 1;
+                    let arg = self.parse_additive()?;
+                    match self.bump() {
+                        Some(Token::Operator(Operator::RParen)) => {
+                            Ok(Expr::Call(name, Box::new(arg)))
+                        }
+                        _ => Err(ParseErrorCode::ExpectedClosingParen),
+                    }
+                } else {
+                    Ok(Expr::Identifier(name))
+                }
+            }
+            Some(Token::Function(function)) => {
+                let args = if let Some(Token::Operator(Operator::LParen)) = self.peek() {
+                    self.pos += 1;
+                    let mut args = vec![self.parse_additive()?];
+                    while let Some(Token::Operator(Operator::Comma)) = self.peek() {
+                        self.pos += 1;
+                        args.push(self.parse_additive()?);
+                    }
+                    match self.bump() {
+                        Some(Token::Operator(Operator::RParen)) => args,
+                        _ => return Err(ParseErrorCode::ExpectedClosingParen),
+                    }
+                } else {
+                    vec![self.parse_unary()?]
+                };
+                Ok(Expr::Function(function, args))
+            }
+            Some(Token::Operator(Operator::LParen)) => {
+                let expr = self.parse_additive()?;
+                match self.bump() {
+                    Some(Token::Operator(Operator::RParen)) => Ok(expr),
+                    _ => Err(ParseErrorCode::ExpectedClosingParen),
+                }
+            }
+            Some(Token::Operator(Operator::Pipe)) => {
+                let expr = self.parse_additive()?;
+                match self.bump() {
+                    Some(Token::Operator(Operator::Pipe)) => {
+                        Ok(Expr::Function(Function::Abs, vec![expr]))
+                    }
+                    _ => Err(ParseErrorCode::ExpectedClosingPipe),
+                }
+            }
+            Some(_) => Err(ParseErrorCode::UnexpectedToken),
+            None => Err(ParseErrorCode::UnexpectedEOF),
+        }
+    }
+}
+
+/// Parses a full token stream into a single `Expr`.
+pub fn parse(tokens: &[Token]) -> Result<Expr, ParseErrorCode> {
+    if tokens.is_empty() {
+        return Err(ParseErrorCode::UnexpectedEOF);
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_expr()?;
+
+    if parser.pos != tokens.len() {
+        return Err(ParseErrorCode::UnexpectedToken);
+    }
+
+    Ok(expr)
+}
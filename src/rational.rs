@@ -0,0 +1,262 @@
+//! Exact rational-number backend: always-reduced `num/den` fractions
+//! implementing `Num`, so e.g. `1/3 + 1/6` comes out as `1/2` instead of a
+//! lossy decimal.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::Num;
+
+/// The denominator used when approximating an irrational result (e.g. a
+/// fractional exponent, or a transcendental function) back into a fraction.
+const APPROXIMATION_SCALE: i128 = 1_000_000_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rational {
+    pub num: i128,
+    pub den: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "Rational denominator must not be zero");
+
+        let sign = if den < 0 { -1 } else { 1 };
+        let (num, den) = (num * sign, den * sign);
+        let divisor = gcd(num, den).max(1);
+
+        Rational {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    fn from_f64_approx(value: f64) -> Rational {
+        Rational::new((value * APPROXIMATION_SCALE as f64).round() as i128, APPROXIMATION_SCALE)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Self) -> Rational {
+        Rational::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Self) -> Rational {
+        Rational::new(
+            self.num * other.den - other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Self) -> Rational {
+        Rational::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics on division by zero; prefer `checked_div`.
+    fn div(self, other: Self) -> Rational {
+        self.checked_div(other).expect("division by zero")
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Num for Rational {
+    fn from_f64(value: f64) -> Self {
+        if value.fract() == 0.0 {
+            Rational::new(value as i128, 1)
+        } else {
+            Rational::from_f64_approx(value)
+        }
+    }
+
+    fn checked_div(self, other: Self) -> Option<Self> {
+        if other.num == 0 {
+            return None;
+        }
+        Some(Rational::new(self.num * other.den, self.den * other.num))
+    }
+
+    fn checked_rem(self, other: Self) -> Option<Self> {
+        if other.num == 0 {
+            None
+        } else {
+            Some(self.rem(other))
+        }
+    }
+
+    fn neg(self) -> Self {
+        Rational::new(-self.num, self.den)
+    }
+
+    /// The rational remainder: `self - other * (self / other).trunc()`.
+    fn rem(self, other: Self) -> Self {
+        let quotient = self.num * other.den;
+        let divisor = self.den * other.num;
+        let truncated = quotient / divisor;
+        self - Rational::new(truncated, 1) * other
+    }
+
+    /// Integer exponents stay exact; fractional exponents fall back to an
+    /// `f64`-approximated fraction.
+    fn pow(self, exponent: Self) -> Self {
+        if exponent.is_integer() {
+            let exp = exponent.num;
+            if exp >= 0 {
+                Rational::new(self.num.pow(exp as u32), self.den.pow(exp as u32))
+            } else {
+                Rational::new(self.den.pow((-exp) as u32), self.num.pow((-exp) as u32))
+            }
+        } else {
+            Rational::from_f64_approx(self.to_f64().powf(exponent.to_f64()))
+        }
+    }
+
+    /// `Err` when a zero base is raised to a negative exponent, which would
+    /// otherwise reach `Rational::new` with a zero denominator and panic.
+    fn checked_pow(self, exponent: Self) -> Result<Self, crate::MathError> {
+        if self.num == 0 && exponent.is_integer() && exponent.num < 0 {
+            return Err(crate::MathError::DivideByZero);
+        }
+        Ok(Num::pow(self, exponent))
+    }
+
+    fn sqrt(self) -> Self {
+        Rational::from_f64_approx(self.to_f64().sqrt())
+    }
+
+    fn sin(self) -> Self {
+        Rational::from_f64_approx(self.to_f64().sin())
+    }
+
+    fn cos(self) -> Self {
+        Rational::from_f64_approx(self.to_f64().cos())
+    }
+
+    fn tan(self) -> Self {
+        Rational::from_f64_approx(self.to_f64().tan())
+    }
+
+    fn log10(self) -> Self {
+        Rational::from_f64_approx(self.to_f64().log10())
+    }
+
+    fn abs(self) -> Self {
+        Rational::new(self.num.abs(), self.den)
+    }
+
+    fn i() -> Self {
+        Rational::new(0, 1)
+    }
+
+    fn factorial(self) -> Self {
+        Rational::from_f64_approx(crate::computer::factorial_approx(self.to_f64()))
+    }
+
+    fn checked_factorial(self) -> Result<Self, crate::MathError> {
+        if self.is_integer() && self.num < 0 {
+            Err(crate::MathError::FactorialOfNegative)
+        } else {
+            Ok(Num::factorial(self))
+        }
+    }
+
+    fn checked_sqrt(self) -> Result<Self, crate::Function> {
+        if self.num < 0 {
+            Err(crate::Function::Sqrt)
+        } else {
+            Ok(Num::sqrt(self))
+        }
+    }
+
+    fn checked_log10(self) -> Result<Self, crate::Function> {
+        if self.num <= 0 {
+            Err(crate::Function::Log)
+        } else {
+            Ok(Num::log10(self))
+        }
+    }
+
+    fn checked_tan(self) -> Result<Self, crate::Function> {
+        if crate::computer::is_tan_pole(self.to_f64()) {
+            Err(crate::Function::Tan)
+        } else {
+            Ok(Num::tan(self))
+        }
+    }
+
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_to_a_negative_exponent_is_divide_by_zero() {
+        let zero = Rational::new(0, 1);
+        let neg_one = Rational::new(-1, 1);
+        assert_eq!(
+            zero.checked_pow(neg_one),
+            Err(crate::MathError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn checked_pow_otherwise_matches_pow() {
+        let two = Rational::new(2, 1);
+        let three = Rational::new(3, 1);
+        assert_eq!(two.checked_pow(three), Ok(Num::pow(two, three)));
+    }
+
+    #[test]
+    fn factorial_of_negative_integer_is_a_domain_error() {
+        let neg_one = Rational::new(-1, 1);
+        assert_eq!(
+            neg_one.checked_factorial(),
+            Err(crate::MathError::FactorialOfNegative)
+        );
+    }
+}